@@ -0,0 +1,58 @@
+use super::driver::{DhtKind, DhtReading, DhtSensor};
+
+/// Marker selecting DHT22/AM2302 decoding: signed 16-bit fixed-point
+/// humidity/temperature fields instead of the DHT11's integral-plus-
+/// tenths bytes.
+pub struct Dht22Kind;
+
+impl DhtKind for Dht22Kind {
+    fn decode(data: [u8; 4]) -> DhtReading {
+        let humidity = (((data[0] as u16) << 8) | data[1] as u16) as f32 * 0.1;
+
+        let temperature_raw = (((data[2] & 0x7F) as u16) << 8) | data[3] as u16;
+        let temperature = if data[2] & 0x80 != 0 {
+            -(temperature_raw as f32) * 0.1
+        } else {
+            temperature_raw as f32 * 0.1
+        };
+
+        DhtReading {
+            temperature,
+            humidity,
+        }
+    }
+}
+
+/// Driver for the pin-compatible DHT22/AM2302, which shares the DHT11's
+/// start-signal and 40-bit frame timing but reports humidity and
+/// temperature as signed 16-bit fixed-point fields instead of an
+/// integral byte plus a tenths byte. See [`DhtSensor`] for the shared
+/// implementation.
+pub type Dht22<P> = DhtSensor<P, Dht22Kind>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "{actual} not within 0.01 of {expected}"
+        );
+    }
+
+    #[test]
+    fn decodes_positive_temperature() {
+        let reading = Dht22Kind::decode([0x02, 0x8c, 0x01, 0x06]);
+        assert_close(reading.humidity, 65.2);
+        assert_close(reading.temperature, 26.2);
+    }
+
+    #[test]
+    fn decodes_negative_temperature() {
+        // data[2]'s top bit (0x80) marks the temperature as negative.
+        let reading = Dht22Kind::decode([0x02, 0x8c, 0x81, 0x05]);
+        assert_close(reading.humidity, 65.2);
+        assert_close(reading.temperature, -26.1);
+    }
+}
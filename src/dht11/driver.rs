@@ -0,0 +1,417 @@
+use core::marker::PhantomData;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin};
+
+#[derive(Debug, Clone, Copy, defmt::Format)]
+pub struct DhtReading {
+    pub temperature: f32,
+    pub humidity: f32,
+}
+
+#[derive(Debug, defmt::Format)]
+pub enum DhtError<E> {
+    Timeout,
+    ChecksumError,
+    Pin(E),
+}
+
+impl<E> From<E> for DhtError<E> {
+    fn from(err: E) -> Self {
+        DhtError::Pin(err)
+    }
+}
+
+/// The DHT11/DHT22 datasheets require at least this much spacing between
+/// the end of one read and the start of the next.
+pub const MIN_RETRY_INTERVAL_MS: u32 = 2000;
+
+/// Running counters for a sequence of reads, useful for monitoring
+/// sensor health in long-running loggers.
+#[derive(Debug, Default, Clone, Copy, defmt::Format)]
+pub struct DhtStats {
+    pub successes: u32,
+    pub checksum_failures: u32,
+    pub timeouts: u32,
+}
+
+impl DhtStats {
+    pub(super) fn record<E>(&mut self, result: &Result<DhtReading, DhtError<E>>) {
+        match result {
+            Ok(_) => self.successes += 1,
+            Err(DhtError::ChecksumError) => self.checksum_failures += 1,
+            Err(DhtError::Timeout) => self.timeouts += 1,
+            Err(DhtError::Pin(_)) => {}
+        }
+    }
+}
+
+/// Decodes the 4-byte payload of a verified DHT frame (`[humidity_hi,
+/// humidity_lo, temp_hi, temp_lo]`) into a [`DhtReading`]. The DHT11 and
+/// DHT22 share everything about the protocol except this step, so
+/// [`DhtSensor`] is generic over it instead of duplicating the rest of
+/// the driver per sensor.
+pub trait DhtKind {
+    fn decode(data: [u8; 4]) -> DhtReading;
+}
+
+/// Marker selecting DHT11 decoding: each field is an integral byte plus
+/// a tenths byte.
+pub struct Dht11Kind;
+
+impl DhtKind for Dht11Kind {
+    fn decode(data: [u8; 4]) -> DhtReading {
+        DhtReading {
+            humidity: data[0] as f32 + (data[1] as f32) * 0.1,
+            temperature: data[2] as f32 + (data[3] as f32) * 0.1,
+        }
+    }
+}
+
+/// DHT11/DHT22 driver, generic over the sensor's frame decoding (`K`),
+/// any `embedded-hal` pin wired open-drain on the data line (so
+/// `set_high`/`set_low` also govern what `is_high`/`is_low` read back),
+/// and any `embedded-hal` delay source.
+pub struct DhtSensor<P, K> {
+    pin: P,
+    stats: DhtStats,
+    _kind: PhantomData<K>,
+}
+
+/// DHT11 driver. See [`DhtSensor`] for the shared implementation.
+pub type Dht11<P> = DhtSensor<P, Dht11Kind>;
+
+impl<P, E, K: DhtKind> DhtSensor<P, K>
+where
+    P: InputPin<Error = E> + OutputPin<Error = E>,
+{
+    /// Create a new driver
+    ///
+    /// # Arguments
+    /// * `pin` - open-drain GPIO pin for the sensor's data line
+    pub fn new(mut pin: P) -> Result<Self, E> {
+        pin.set_high()?;
+        Ok(Self {
+            pin,
+            stats: DhtStats::default(),
+            _kind: PhantomData,
+        })
+    }
+
+    pub fn perform_measurement<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<DhtReading, DhtError<E>> {
+        let result = read_frame(&mut self.pin, delay).map(K::decode);
+        self.stats.record(&result);
+        result
+    }
+
+    /// Retry [`Self::perform_measurement`] up to `attempts` times,
+    /// enforcing the mandatory [`MIN_RETRY_INTERVAL_MS`] spacing between
+    /// tries. Returns the first successful reading, or the last error if
+    /// every attempt failed.
+    ///
+    /// The inter-attempt spacing is a full 2 seconds, so it's awaited
+    /// through `embedded-hal-async`'s `DelayNs` rather than the blocking
+    /// `embedded-hal` one `perform_measurement` uses for microsecond-
+    /// scale pulse timing - busy-spinning the CPU for 2s per retry would
+    /// starve every other task on an async executor.
+    pub async fn read_with_retries<D>(
+        &mut self,
+        delay: &mut D,
+        attempts: u8,
+    ) -> Result<DhtReading, DhtError<E>>
+    where
+        D: DelayNs + embedded_hal_async::delay::DelayNs,
+    {
+        let mut last_err = None;
+
+        for attempt in 0..attempts.max(1) {
+            if attempt > 0 {
+                embedded_hal_async::delay::DelayNs::delay_ms(delay, MIN_RETRY_INTERVAL_MS).await;
+            }
+
+            match self.perform_measurement(delay) {
+                Ok(reading) => return Ok(reading),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err.expect("attempts is at least 1"))
+    }
+
+    /// Read-only view of this driver's running success/failure counters.
+    pub fn stats(&self) -> DhtStats {
+        self.stats
+    }
+}
+
+/// Run the shared start-signal + 40-bit sampling sequence common to the
+/// DHT11 and DHT22, returning the verified 4-byte payload (`[humidity_hi,
+/// humidity_lo, temp_hi, temp_lo]`) for the caller to decode.
+pub(super) fn read_frame<P, E, D>(pin: &mut P, delay: &mut D) -> Result<[u8; 4], DhtError<E>>
+where
+    P: InputPin<Error = E> + OutputPin<Error = E>,
+    D: DelayNs,
+{
+    // Send start signal: pull low for at least 18ms
+    pin.set_low()?;
+    delay.delay_ms(20);
+
+    // Release the line and wait 20-40us for the sensor to respond
+    pin.set_high()?;
+    delay.delay_us(40);
+
+    // Wait for the sensor to pull low (response signal)
+    wait_for_low(pin, delay, 100)?;
+
+    // Wait for the sensor to pull high
+    wait_for_high(pin, delay, 100)?;
+
+    // Wait for the sensor to pull low (start of data)
+    wait_for_low(pin, delay, 100)?;
+
+    // Read 40 bits of data
+    let mut data = [0u8; 5];
+
+    for byte in data.iter_mut() {
+        for bit in (0..8).rev() {
+            // Wait for start of bit (high)
+            wait_for_high(pin, delay, 100)?;
+
+            // Measure high pulse duration
+            // '0' = ~28us high, '1' = ~70us high
+            let mut high_time = 0u32;
+            while pin.is_high()? && high_time < 200 {
+                delay.delay_us(1);
+                high_time += 1;
+            }
+
+            // Adjust threshold based on measured values:
+            // Short pulse (0): ~8-12us, Long pulse (1): ~30-34us
+            // Use 20us as threshold
+            if high_time > 20 {
+                *byte |= 1 << bit;
+            }
+
+            // Wait for end of bit (low)
+            wait_for_low(pin, delay, 100)?;
+        }
+    }
+
+    // Release the line to finish
+    pin.set_high()?;
+
+    defmt::debug!("Raw data: [{:02x}, {:02x}, {:02x}, {:02x}, {:02x}]",
+                  data[0], data[1], data[2], data[3], data[4]);
+
+    // Verify checksum
+    let checksum = data[0]
+        .wrapping_add(data[1])
+        .wrapping_add(data[2])
+        .wrapping_add(data[3]);
+
+    defmt::debug!("Calculated checksum: {:02x}, Received: {:02x}", checksum, data[4]);
+
+    if checksum != data[4] {
+        return Err(DhtError::ChecksumError);
+    }
+
+    Ok([data[0], data[1], data[2], data[3]])
+}
+
+fn wait_for_low<P, E, D>(pin: &mut P, delay: &mut D, timeout_us: u32) -> Result<(), DhtError<E>>
+where
+    P: InputPin<Error = E>,
+    D: DelayNs,
+{
+    let mut count = 0;
+    while pin.is_high()? {
+        count += 1;
+        if count > timeout_us {
+            return Err(DhtError::Timeout);
+        }
+        delay.delay_us(1);
+    }
+    Ok(())
+}
+
+fn wait_for_high<P, E, D>(pin: &mut P, delay: &mut D, timeout_us: u32) -> Result<(), DhtError<E>>
+where
+    P: InputPin<Error = E>,
+    D: DelayNs,
+{
+    let mut count = 0;
+    while pin.is_low()? {
+        count += 1;
+        if count > timeout_us {
+            return Err(DhtError::Timeout);
+        }
+        delay.delay_us(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_hal::digital::ErrorType;
+
+    use super::*;
+
+    /// A scripted data-line level at each successive `is_high`/`is_low`
+    /// query, replaying one or more attempts back-to-back the same way
+    /// the real open-drain line would present them to [`read_frame`].
+    #[derive(Default)]
+    struct ScriptedPin {
+        levels: std::vec::Vec<bool>,
+        next: usize,
+    }
+
+    impl ScriptedPin {
+        /// 101 consecutive high readings: enough for [`wait_for_low`]'s
+        /// first call (timeout `100`) to give up without ever seeing a
+        /// real frame.
+        fn push_response_timeout(&mut self) {
+            self.levels.extend(std::iter::repeat_n(true, 101));
+        }
+
+        /// One full, checksum-valid DHT11 frame decoding to `payload`.
+        fn push_valid_frame(&mut self, payload: [u8; 4]) {
+            let checksum = payload[0]
+                .wrapping_add(payload[1])
+                .wrapping_add(payload[2])
+                .wrapping_add(payload[3]);
+            let data = [payload[0], payload[1], payload[2], payload[3], checksum];
+
+            // Response pulse (high, then low, then high, then low again
+            // at the start of data) - durations are arbitrary, only the
+            // transitions matter.
+            self.wait_high_then_low(3);
+            self.wait_low_then_high(3);
+            self.wait_high_then_low(3);
+
+            for byte in data {
+                for bit in (0..8).rev() {
+                    self.wait_low_then_high(2);
+                    let high_ticks = if byte & (1 << bit) != 0 { 30 } else { 10 };
+                    self.wait_high_then_low(high_ticks);
+                    // The explicit "wait for end of bit" query after the
+                    // measuring loop above - the line is already low, so
+                    // this is answered on its first query.
+                    self.levels.push(false);
+                }
+            }
+        }
+
+        fn wait_high_then_low(&mut self, high_ticks: usize) {
+            self.levels.extend(std::iter::repeat_n(true, high_ticks));
+            self.levels.push(false);
+        }
+
+        fn wait_low_then_high(&mut self, low_ticks: usize) {
+            self.levels.extend(std::iter::repeat_n(false, low_ticks));
+            self.levels.push(true);
+        }
+
+        fn next_level(&mut self) -> bool {
+            let level = self.levels[self.next.min(self.levels.len() - 1)];
+            self.next += 1;
+            level
+        }
+    }
+
+    impl ErrorType for ScriptedPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for ScriptedPin {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    impl InputPin for ScriptedPin {
+        fn is_high(&mut self) -> Result<bool, Infallible> {
+            Ok(self.next_level())
+        }
+
+        fn is_low(&mut self) -> Result<bool, Infallible> {
+            Ok(!self.next_level())
+        }
+    }
+
+    /// Delay that doesn't actually wait - read_with_retries's 2-second
+    /// inter-attempt spacing would otherwise make this test glacial.
+    struct NoDelay;
+
+    impl DelayNs for NoDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    impl embedded_hal_async::delay::DelayNs for NoDelay {
+        async fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    /// Drive a future to completion without pulling in an async test
+    /// runner - `NoDelay` never actually pends, so every future here
+    /// resolves on its first poll.
+    fn block_on<F: core::future::Future>(fut: F) -> F::Output {
+        use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+        fn noop(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(core::ptr::null(), &VTABLE)
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = std::boxed::Box::pin(fut);
+        loop {
+            if let Poll::Ready(v) = fut.as_mut().poll(&mut cx) {
+                return v;
+            }
+        }
+    }
+
+    #[test]
+    fn read_with_retries_returns_a_late_success_and_tallies_the_failure() {
+        let mut pin = ScriptedPin::default();
+        pin.push_response_timeout();
+        pin.push_valid_frame([0x32, 0x00, 0x15, 0x00]);
+
+        let mut dht11 = Dht11::new(pin).unwrap();
+        let reading = block_on(dht11.read_with_retries(&mut NoDelay, 2)).unwrap();
+
+        assert_eq!(reading.humidity, 50.0);
+        assert_eq!(reading.temperature, 21.0);
+
+        let stats = dht11.stats();
+        assert_eq!(stats.successes, 1);
+        assert_eq!(stats.timeouts, 1);
+        assert_eq!(stats.checksum_failures, 0);
+    }
+
+    #[test]
+    fn read_with_retries_reports_the_last_error_once_exhausted() {
+        let mut pin = ScriptedPin::default();
+        pin.push_response_timeout();
+        pin.push_response_timeout();
+
+        let mut dht11 = Dht11::new(pin).unwrap();
+        let result = block_on(dht11.read_with_retries(&mut NoDelay, 2));
+
+        assert!(matches!(result, Err(DhtError::Timeout)));
+
+        let stats = dht11.stats();
+        assert_eq!(stats.successes, 0);
+        assert_eq!(stats.timeouts, 2);
+    }
+}
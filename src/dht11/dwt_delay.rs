@@ -1,6 +1,19 @@
 use cortex_m::peripheral::DWT;
+use embedded_hal::delay::DelayNs;
 
-/// DWT-based delay provider that works with any CPU frequency
+/// DWT-based delay provider that works with any CPU frequency.
+///
+/// This is one concrete `embedded-hal` `DelayNs` implementation for
+/// Cortex-M parts with a cycle counter; it's gated behind the `dwt-delay`
+/// feature so users on other platforms (RP2040, nRF, ...) can supply
+/// their own delay instead. It only implements the blocking
+/// `embedded-hal` `DelayNs`, not the async `embedded-hal-async` one, so
+/// it can't drive [`super::Dht11::read_with_retries`]'s inter-attempt
+/// spacing without busy-blocking the executor - pair it with
+/// `perform_measurement` directly, or use an async-capable delay (e.g.
+/// `embassy_time::Delay`) for retries. The bundled `main.rs` example
+/// uses `embassy_time::Delay` for both, so it builds with this feature
+/// on or off.
 pub struct DwtDelay {
     cycles_per_us: u32,
 }
@@ -12,16 +25,17 @@ impl DwtDelay {
             cycles_per_us: cpu_freq_hz / 1_000_000,
         }
     }
+}
 
-    /// Delay for approximately 1 microsecond
-    #[inline(always)]
-    pub fn delay_1us(&self) {
+impl DelayNs for DwtDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        let cycles = ((ns as u64 * self.cycles_per_us as u64) / 1_000) as u32;
         let start = DWT::cycle_count();
 
         loop {
             let current = DWT::cycle_count();
             let elapsed = current.wrapping_sub(start);
-            if elapsed >= self.cycles_per_us {
+            if elapsed >= cycles {
                 break;
             }
         }
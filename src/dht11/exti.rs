@@ -0,0 +1,192 @@
+use core::marker::PhantomData;
+
+use embassy_futures::select::{select, Either};
+use embassy_stm32::exti::ExtiInput;
+use embassy_stm32::gpio::{Flex, Input, Pin as GpioPin, Pull, Speed};
+use embassy_stm32::{into_ref, Peripheral, PeripheralRef};
+use embassy_time::{Duration, Instant, Timer};
+
+use super::driver::{DhtError, DhtKind, DhtReading, DhtStats, MIN_RETRY_INTERVAL_MS};
+use super::dht22::Dht22Kind;
+use super::Dht11Kind;
+
+/// A measured high pulse longer than this is decoded as a `1` bit,
+/// shorter is decoded as a `0` bit.
+const ONE_BIT_THRESHOLD_US: u64 = 50;
+
+/// How long to wait for any single edge before giving up on the frame.
+const EDGE_TIMEOUT: Duration = Duration::from_micros(100);
+
+/// Edge-driven async DHT11/DHT22 reader built on embassy's `ExtiInput`,
+/// as an alternative to [`super::DhtSensor`]'s busy-wait sampling.
+///
+/// Instead of spinning on `is_high()`/`is_low()` for the whole ~4 ms
+/// frame, each bit is decoded by `.await`ing the rising edge that starts
+/// it, then the falling edge that ends it, and measuring the elapsed
+/// time between the two. This frees the executor to run other tasks
+/// between transitions instead of pinning the core in a spin loop.
+///
+/// The data pin is reconfigured twice per read: a plain [`Flex`] drives
+/// the ~18ms low start pulse, then an [`ExtiInput`] takes over (holding
+/// the reserved EXTI line) for the sensor's response and data bits,
+/// since edge-wait futures are only available on `ExtiInput`, not `Flex`.
+///
+/// Generic over the frame decoding (`K`), same as [`super::DhtSensor`] -
+/// see [`Dht11Exti`]/[`Dht22Exti`].
+///
+/// Tracks the same running [`DhtStats`] and offers the same
+/// [`Self::read_with_retries`] as [`super::DhtSensor`] - `read` already
+/// being async here just means there's no separate blocking
+/// `perform_measurement` step to hang them off.
+pub struct ExtiSensor<'d, T: GpioPin, K> {
+    pin: PeripheralRef<'d, T>,
+    channel: PeripheralRef<'d, T::ExtiChannel>,
+    stats: DhtStats,
+    _kind: PhantomData<K>,
+}
+
+/// EXTI-driven DHT11 reader. See [`ExtiSensor`] for the shared implementation.
+pub type Dht11Exti<'d, T> = ExtiSensor<'d, T, Dht11Kind>;
+/// EXTI-driven DHT22 reader. See [`ExtiSensor`] for the shared implementation.
+pub type Dht22Exti<'d, T> = ExtiSensor<'d, T, Dht22Kind>;
+
+impl<'d, T: GpioPin, K: DhtKind> ExtiSensor<'d, T, K>
+where
+    T::ExtiChannel: Peripheral<P = T::ExtiChannel>,
+{
+    pub fn new(pin: impl Peripheral<P = T> + 'd, channel: impl Peripheral<P = T::ExtiChannel> + 'd) -> Self {
+        into_ref!(pin, channel);
+
+        let mut flex = Flex::new(pin.reborrow());
+        flex.set_as_output(Speed::VeryHigh);
+        flex.set_high();
+        drop(flex);
+
+        Self {
+            pin,
+            channel,
+            stats: DhtStats::default(),
+            _kind: PhantomData,
+        }
+    }
+
+    /// Read one frame, recording the outcome into [`Self::stats`].
+    pub async fn read(&mut self) -> Result<DhtReading, DhtError<core::convert::Infallible>> {
+        let result = self.read_inner().await;
+        self.stats.record(&result);
+        result
+    }
+
+    /// Retry [`Self::read`] up to `attempts` times, enforcing the
+    /// mandatory [`MIN_RETRY_INTERVAL_MS`] spacing between tries. Returns
+    /// the first successful reading, or the last error if every attempt
+    /// failed. See [`super::DhtSensor::read_with_retries`].
+    pub async fn read_with_retries<D>(
+        &mut self,
+        delay: &mut D,
+        attempts: u8,
+    ) -> Result<DhtReading, DhtError<core::convert::Infallible>>
+    where
+        D: embedded_hal_async::delay::DelayNs,
+    {
+        let mut last_err = None;
+
+        for attempt in 0..attempts.max(1) {
+            if attempt > 0 {
+                delay.delay_ms(MIN_RETRY_INTERVAL_MS).await;
+            }
+
+            match self.read().await {
+                Ok(reading) => return Ok(reading),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err.expect("attempts is at least 1"))
+    }
+
+    /// Read-only view of this sensor's running success/failure counters.
+    pub fn stats(&self) -> DhtStats {
+        self.stats
+    }
+
+    async fn read_inner(&mut self) -> Result<DhtReading, DhtError<core::convert::Infallible>> {
+        // Send start signal: pull low for at least 18ms
+        {
+            let mut flex = Flex::new(self.pin.reborrow());
+            flex.set_as_output(Speed::VeryHigh);
+            flex.set_low();
+            Timer::after_millis(20).await;
+
+            // Release the line and wait 20-40us for the sensor to respond
+            flex.set_high();
+            Timer::after_micros(40).await;
+        }
+
+        // Hand the line over to EXTI for the rest of the frame
+        let input = Input::new(self.pin.reborrow(), Pull::Up);
+        let mut exti = ExtiInput::new(input, self.channel.reborrow());
+
+        // Response signal: low, then high, then low again at the start of data
+        Self::wait_for_edge(&mut exti, Edge::Falling).await?;
+        Self::wait_for_edge(&mut exti, Edge::Rising).await?;
+        Self::wait_for_edge(&mut exti, Edge::Falling).await?;
+
+        // Read 40 bits of data, each bit being one rising/falling pair
+        let mut data = [0u8; 5];
+        for byte in data.iter_mut() {
+            for bit in (0..8).rev() {
+                Self::wait_for_edge(&mut exti, Edge::Rising).await?;
+                let rose_at = Instant::now();
+
+                Self::wait_for_edge(&mut exti, Edge::Falling).await?;
+                let high_time = Instant::now() - rose_at;
+
+                if high_time.as_micros() > ONE_BIT_THRESHOLD_US {
+                    *byte |= 1 << bit;
+                }
+            }
+        }
+        drop(exti);
+
+        // Release the line to finish
+        let mut flex = Flex::new(self.pin.reborrow());
+        flex.set_as_output(Speed::VeryHigh);
+        flex.set_high();
+
+        defmt::debug!("Raw data: [{:02x}, {:02x}, {:02x}, {:02x}, {:02x}]",
+                      data[0], data[1], data[2], data[3], data[4]);
+
+        let checksum = data[0]
+            .wrapping_add(data[1])
+            .wrapping_add(data[2])
+            .wrapping_add(data[3]);
+
+        if checksum != data[4] {
+            return Err(DhtError::ChecksumError);
+        }
+
+        Ok(K::decode([data[0], data[1], data[2], data[3]]))
+    }
+
+    async fn wait_for_edge(
+        exti: &mut ExtiInput<'_, T>,
+        edge: Edge,
+    ) -> Result<(), DhtError<core::convert::Infallible>> {
+        let result = match edge {
+            Edge::Rising => select(exti.wait_for_rising_edge(), Timer::after(EDGE_TIMEOUT)).await,
+            Edge::Falling => select(exti.wait_for_falling_edge(), Timer::after(EDGE_TIMEOUT)).await,
+        };
+
+        match result {
+            Either::First(()) => Ok(()),
+            Either::Second(()) => Err(DhtError::Timeout),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Edge {
+    Rising,
+    Falling,
+}
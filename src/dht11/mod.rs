@@ -0,0 +1,17 @@
+mod dht22;
+mod driver;
+#[cfg(feature = "dwt-delay")]
+mod dwt_delay;
+#[cfg(feature = "embassy-exti")]
+mod exti;
+#[cfg(feature = "timer-capture")]
+mod timer_capture;
+
+pub use driver::{Dht11, Dht11Kind, DhtError, DhtKind, DhtReading, DhtSensor, DhtStats, MIN_RETRY_INTERVAL_MS};
+pub use dht22::{Dht22, Dht22Kind};
+#[cfg(feature = "dwt-delay")]
+pub use dwt_delay::DwtDelay;
+#[cfg(feature = "embassy-exti")]
+pub use exti::{Dht11Exti, Dht22Exti};
+#[cfg(feature = "timer-capture")]
+pub use timer_capture::{Ch1, Ch2, Ch3, Ch4, Dht11TimerCapture, Dht22TimerCapture};
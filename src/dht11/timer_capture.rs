@@ -0,0 +1,405 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::sync::atomic::{compiler_fence, Ordering};
+
+use embassy_futures::select::{select, Either};
+use embassy_stm32::dma::{Channel as DmaChannel, Request as DmaRequest, Transfer};
+use embassy_stm32::gpio::low_level::{AFType, Pin as _};
+use embassy_stm32::gpio::{AnyPin, Flex, Speed};
+use embassy_stm32::timer::{
+    CaptureCompare16bitInstance, Channel, Channel1Pin, Channel2Pin, Channel3Pin, Channel4Pin, InputCaptureMode,
+    InputTISelection,
+};
+use embassy_stm32::{into_ref, Peripheral, PeripheralRef};
+use embassy_time::{Duration, Timer};
+
+use super::dht22::Dht22Kind;
+use super::driver::{DhtError, DhtKind, DhtReading, DhtStats, MIN_RETRY_INTERVAL_MS};
+use super::Dht11Kind;
+
+/// Total edge timestamps captured for one full frame: the response
+/// pulse contributes two edges (falling, then rising) and start-of-data
+/// one more falling edge (3 total), and each of the 40 data bits
+/// contributes a rising and a falling edge.
+pub const CAPTURE_LEN: usize = 3 + 40 * 2;
+
+/// A captured frame shorter than this many data bits is treated as
+/// truncated rather than decoded.
+const MIN_DATA_BITS: usize = 40;
+
+/// Timer ticks equivalent to the ~50us 0/1 threshold used by the
+/// EXTI backend. Callers are expected to configure the timer's
+/// prescaler so one tick is 1us, so this threshold lines up directly
+/// with capture-to-capture differences.
+const HIGH_THRESHOLD_TICKS: u32 = 50;
+
+/// Frame-wide deadline for the DMA capture. A DMA-filled buffer has no
+/// natural per-edge await to race against a timeout the way
+/// [`super::exti::ExtiSensor`] does, so instead the whole transfer is
+/// bounded by one deadline covering the ~4ms frame: if the sensor never
+/// shows up, or stops short of `CAPTURE_LEN` edges, the transfer is
+/// aborted and the read reported as timed out rather than hanging
+/// forever.
+const FRAME_TIMEOUT: Duration = Duration::from_millis(5);
+
+/// Channel 1 marker type. See [`TimerCaptureSensor::new_ch1`].
+pub enum Ch1 {}
+/// Channel 2 marker type. See [`TimerCaptureSensor::new_ch2`].
+pub enum Ch2 {}
+/// Channel 3 marker type. See [`TimerCaptureSensor::new_ch3`].
+pub enum Ch3 {}
+/// Channel 4 marker type. See [`TimerCaptureSensor::new_ch4`].
+pub enum Ch4 {}
+
+/// Maps a channel marker type to the runtime [`Channel`] it stands for, so
+/// the rest of the driver can still index `T::regs_gp16()` without holding
+/// a runtime-checked `Channel` field of its own.
+pub trait CaptureChannel {
+    const CHANNEL: Channel;
+}
+
+impl CaptureChannel for Ch1 {
+    const CHANNEL: Channel = Channel::Ch1;
+}
+impl CaptureChannel for Ch2 {
+    const CHANNEL: Channel = Channel::Ch2;
+}
+impl CaptureChannel for Ch3 {
+    const CHANNEL: Channel = Channel::Ch3;
+}
+impl CaptureChannel for Ch4 {
+    const CHANNEL: Channel = Channel::Ch4;
+}
+
+/// Timer input-capture + DMA based DHT11/DHT22 reader.
+///
+/// The timer channel is fixed at construction time by which `new_chN`
+/// constructor is called, rather than by a runtime [`Channel`] argument:
+/// each constructor only accepts a pin that implements the matching
+/// `ChannelNPin<T>` trait, the same way [`embassy_stm32::timer::qei::QeiPin`]
+/// and `SimplePwm`'s `PwmPin` pick their alternate function. That typed
+/// pin is what supplies `af_num()` - the AF number wired to this exact
+/// timer/channel/pin combination on the target MCU - which is routed onto
+/// the pin with `set_as_af` before every capture. Without that call the
+/// pin stays a plain GPIO input and the timer never sees an edge.
+///
+/// Channel `Ch` is configured for both-edge input capture with its
+/// capture-compare DMA request enabled (`CCxDE`), so a DMA channel
+/// streams every edge's timestamp straight out of the timer's
+/// capture/compare register into `captures`, with no CPU or ISR
+/// involvement during the frame. Unlike [`super::exti::ExtiSensor`],
+/// where a busy executor or ISR backlog can delay servicing an edge and
+/// shift the rest of the frame, the DMA transfer can't be starved by
+/// software load, so bit decoding stays immune to jitter near the
+/// 26-50us high-phase threshold regardless of what else the core is
+/// doing.
+///
+/// Generic over the frame decoding (`K`), same as [`super::DhtSensor`] -
+/// see [`Dht11TimerCapture`]/[`Dht22TimerCapture`].
+///
+/// This type does not drive the open-drain start pulse through a timer
+/// peripheral: the shared data pin is reconfigured twice per read, the
+/// same way [`super::exti::ExtiSensor`] does it, since a timer channel
+/// pin can't be driven as a plain GPIO output while armed for capture.
+/// Between reads the pin is left as a `Flex` output so nothing but `read`
+/// ever has to touch the AF registers directly.
+///
+/// Tracks the same running [`DhtStats`] and offers the same
+/// [`Self::read_with_retries`] as [`super::DhtSensor`]/[`super::exti::ExtiSensor`].
+pub struct TimerCaptureSensor<'d, T: CaptureCompare16bitInstance, Dma: DmaChannel, Ch, K> {
+    pin: PeripheralRef<'d, AnyPin>,
+    af_num: u8,
+    timer: PeripheralRef<'d, T>,
+    dma: PeripheralRef<'d, Dma>,
+    dma_request: DmaRequest,
+    stats: DhtStats,
+    _channel: PhantomData<Ch>,
+    _kind: PhantomData<K>,
+}
+
+/// Timer-capture + DMA driven DHT11 reader. See [`TimerCaptureSensor`] for
+/// the shared implementation.
+pub type Dht11TimerCapture<'d, T, Dma, Ch> = TimerCaptureSensor<'d, T, Dma, Ch, Dht11Kind>;
+/// Timer-capture + DMA driven DHT22 reader. See [`TimerCaptureSensor`] for
+/// the shared implementation.
+pub type Dht22TimerCapture<'d, T, Dma, Ch> = TimerCaptureSensor<'d, T, Dma, Ch, Dht22Kind>;
+
+macro_rules! channel_ctor {
+    ($new_chx:ident, $channel:ident, $pin_trait:ident) => {
+        impl<'d, T, Dma, K> TimerCaptureSensor<'d, T, Dma, $channel, K>
+        where
+            T: CaptureCompare16bitInstance,
+            Dma: DmaChannel,
+            K: DhtKind,
+        {
+            #[doc = concat!("Create a new reader using `timer`'s ", stringify!($channel), ".")]
+            ///
+            /// `dma_request` is the DMA request number wired to `timer`'s
+            /// capture/compare event for this channel on this MCU. This
+            /// crate version predates embassy-stm32's per-channel `ChxDma`
+            /// request traits that derive this automatically, so the
+            /// caller has to look it up in the reference manual's DMA
+            /// request mapping table for the chosen timer/channel/DMA
+            /// channel combination.
+            pub fn $new_chx(
+                pin: impl Peripheral<P = impl $pin_trait<T>> + 'd,
+                timer: impl Peripheral<P = T> + 'd,
+                dma: impl Peripheral<P = Dma> + 'd,
+                dma_request: DmaRequest,
+            ) -> Self {
+                into_ref!(pin, timer, dma);
+                let af_num = pin.af_num();
+
+                let mut flex = Flex::new(pin.reborrow());
+                flex.set_as_output(Speed::VeryHigh);
+                flex.set_high();
+                drop(flex);
+
+                Self {
+                    pin: pin.map_into(),
+                    af_num,
+                    timer,
+                    dma,
+                    dma_request,
+                    stats: DhtStats::default(),
+                    _channel: PhantomData,
+                    _kind: PhantomData,
+                }
+            }
+        }
+    };
+}
+
+channel_ctor!(new_ch1, Ch1, Channel1Pin);
+channel_ctor!(new_ch2, Ch2, Channel2Pin);
+channel_ctor!(new_ch3, Ch3, Channel3Pin);
+channel_ctor!(new_ch4, Ch4, Channel4Pin);
+
+impl<'d, T, Dma, Ch, K> TimerCaptureSensor<'d, T, Dma, Ch, K>
+where
+    T: CaptureCompare16bitInstance,
+    Dma: DmaChannel,
+    Ch: CaptureChannel,
+    K: DhtKind,
+{
+    /// Issue the start pulse, capture the response frame's edges by DMA,
+    /// decode them into a reading, and record the outcome into
+    /// [`Self::stats`].
+    pub async fn read(&mut self) -> Result<DhtReading, DhtError<Infallible>> {
+        let result = self.read_inner().await;
+        self.stats.record(&result);
+        result
+    }
+
+    /// Retry [`Self::read`] up to `attempts` times, enforcing the
+    /// mandatory [`MIN_RETRY_INTERVAL_MS`] spacing between tries. Returns
+    /// the first successful reading, or the last error if every attempt
+    /// failed. See [`super::DhtSensor::read_with_retries`].
+    pub async fn read_with_retries<D>(&mut self, delay: &mut D, attempts: u8) -> Result<DhtReading, DhtError<Infallible>>
+    where
+        D: embedded_hal_async::delay::DelayNs,
+    {
+        let mut last_err = None;
+
+        for attempt in 0..attempts.max(1) {
+            if attempt > 0 {
+                delay.delay_ms(MIN_RETRY_INTERVAL_MS).await;
+            }
+
+            match self.read().await {
+                Ok(reading) => return Ok(reading),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err.expect("attempts is at least 1"))
+    }
+
+    /// Read-only view of this sensor's running success/failure counters.
+    pub fn stats(&self) -> DhtStats {
+        self.stats
+    }
+
+    async fn read_inner(&mut self) -> Result<DhtReading, DhtError<Infallible>> {
+        // Send start signal: pull low for at least 18ms
+        {
+            let mut flex = Flex::new(self.pin.reborrow());
+            flex.set_as_output(Speed::VeryHigh);
+            flex.set_low();
+            Timer::after_millis(20).await;
+
+            // Release the line and wait 20-40us for the sensor to respond
+            flex.set_high();
+            Timer::after_micros(40).await;
+        }
+
+        // Hand the pin to the timer: without this, `arm_capture_dma`
+        // below configures a channel that never sees an edge, since the
+        // pin is still wired as a plain GPIO rather than routed onto the
+        // timer's input through its alternate function.
+        self.pin.set_as_af(self.af_num, AFType::Input);
+
+        let mut captures = [0u32; CAPTURE_LEN];
+        let transfer = self.arm_capture_dma(&mut captures);
+
+        let result = match select(transfer, Timer::after(FRAME_TIMEOUT)).await {
+            Either::First(()) => decode_captures::<K>(&captures),
+            Either::Second(()) => Err(DhtError::Timeout),
+        };
+
+        // Release the line to finish, back as a plain GPIO output so the
+        // next read's start pulse doesn't have to fight the timer for it.
+        let mut flex = Flex::new(self.pin.reborrow());
+        flex.set_as_output(Speed::VeryHigh);
+        flex.set_high();
+
+        result
+    }
+
+    /// Configure `Ch::CHANNEL` for both-edge input capture with its DMA
+    /// request enabled, and start a one-shot DMA transfer that completes
+    /// once `captures` has been filled - one capture-compare timestamp
+    /// per edge, with no CPU involvement in between.
+    fn arm_capture_dma<'a>(&'a mut self, captures: &'a mut [u32; CAPTURE_LEN]) -> Transfer<'a, Dma> {
+        let channel = Ch::CHANNEL;
+        self.timer.set_input_ti_selection(channel, InputTISelection::Normal);
+        self.timer.set_input_capture_mode(channel, InputCaptureMode::BothEdges);
+        self.timer.enable_channel(channel, true);
+
+        // `enable_channel`/`set_input_capture_mode` cover CCxS/CCxP/CCxNP/
+        // CCxE; CCxDE (request DMA on every capture) has no dedicated
+        // method on this crate version's low-level instance trait, so
+        // set it directly through the same register block.
+        let regs = T::regs_gp16();
+        let ch = channel.index();
+        regs.dier().modify(|w| w.set_ccde(ch, true));
+        regs.cr1().modify(|w| w.set_cen(true));
+
+        // Ensure the capture/DMA-enable writes above are visible before
+        // the DMA peripheral starts reading the capture register.
+        compiler_fence(Ordering::SeqCst);
+
+        unsafe {
+            Transfer::new_read(
+                self.dma.reborrow(),
+                self.dma_request,
+                regs.ccr(ch).as_ptr() as *mut u32,
+                captures,
+                Default::default(),
+            )
+        }
+    }
+}
+
+/// Decode a buffer of raw timer-tick edge timestamps into a
+/// [`DhtReading`], thresholding each high-phase duration to classify it
+/// as a `0` or `1` bit.
+///
+/// The response pulse and start-of-data transition are consumed first;
+/// the remaining edges are taken in rising/falling pairs, one pair per
+/// data bit. Capture-to-capture differences are computed with wrapping
+/// arithmetic so a free-running timer counter rolling over mid-frame
+/// doesn't produce a bogus negative width.
+fn decode_captures<K: DhtKind>(captures: &[u32]) -> Result<DhtReading, DhtError<Infallible>> {
+    // Skip the response pulse (low, high) and the falling edge that
+    // starts the first data bit; everything after comes in rising/
+    // falling pairs.
+    let bit_edges = captures.get(3..).unwrap_or(&[]);
+    let bit_count = bit_edges.len() / 2;
+
+    if bit_count < MIN_DATA_BITS {
+        return Err(DhtError::Timeout);
+    }
+
+    let mut data = [0u8; 5];
+    for (i, pair) in bit_edges.chunks_exact(2).take(40).enumerate() {
+        // The timer's counter is 16 bits wide, so wrap at 16 bits.
+        let high_ticks = (pair[1] as u16).wrapping_sub(pair[0] as u16) as u32;
+        if high_ticks > HIGH_THRESHOLD_TICKS {
+            data[i / 8] |= 1 << (7 - (i % 8));
+        }
+    }
+
+    let checksum = data[0]
+        .wrapping_add(data[1])
+        .wrapping_add(data[2])
+        .wrapping_add(data[3]);
+
+    if checksum != data[4] {
+        return Err(DhtError::ChecksumError);
+    }
+
+    Ok(K::decode([data[0], data[1], data[2], data[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a synthetic capture buffer for the given verified payload
+    /// bytes (checksum appended automatically), encoding each bit as a
+    /// rising/falling tick pair either side of `HIGH_THRESHOLD_TICKS`.
+    fn encode_captures(payload: [u8; 4]) -> [u32; CAPTURE_LEN] {
+        let checksum = payload[0]
+            .wrapping_add(payload[1])
+            .wrapping_add(payload[2])
+            .wrapping_add(payload[3]);
+        let data = [payload[0], payload[1], payload[2], payload[3], checksum];
+
+        let mut captures = [0u32; CAPTURE_LEN];
+        // Response pulse + start-of-data edges; their absolute values
+        // don't matter since decode_captures skips them.
+        captures[0] = 0;
+        captures[1] = 100;
+        captures[2] = 200;
+
+        let mut tick = 1000u32;
+        let mut idx = 3;
+        for byte in data {
+            for bit in (0..8).rev() {
+                let high_ticks = if byte & (1 << bit) != 0 {
+                    HIGH_THRESHOLD_TICKS + 20
+                } else {
+                    HIGH_THRESHOLD_TICKS - 20
+                };
+                captures[idx] = tick;
+                captures[idx + 1] = tick + high_ticks;
+                tick += high_ticks + 50;
+                idx += 2;
+            }
+        }
+
+        captures
+    }
+
+    #[test]
+    fn decode_captures_reads_back_encoded_payload() {
+        let captures = encode_captures([0x32, 0x00, 0x15, 0x00]);
+        let reading = decode_captures::<Dht11Kind>(&captures).unwrap();
+        assert_eq!(reading.humidity, 50.0);
+        assert_eq!(reading.temperature, 21.0);
+    }
+
+    #[test]
+    fn decode_captures_rejects_bad_checksum() {
+        let mut captures = encode_captures([0x32, 0x00, 0x15, 0x00]);
+        // The checksum byte's low bit was encoded as a long (`1`) pulse;
+        // shorten it below the threshold so it decodes as `0` instead,
+        // without touching any of the payload bits.
+        let last = captures.len() - 1;
+        captures[last] -= 40;
+        assert!(matches!(
+            decode_captures::<Dht11Kind>(&captures),
+            Err(DhtError::ChecksumError)
+        ));
+    }
+
+    #[test]
+    fn decode_captures_times_out_on_a_short_buffer() {
+        let captures = [0u32; 10];
+        assert!(matches!(
+            decode_captures::<Dht11Kind>(&captures),
+            Err(DhtError::Timeout)
+        ));
+    }
+}
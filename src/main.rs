@@ -4,13 +4,12 @@
 
 use defmt::info;
 use embassy_executor::Spawner;
-use embassy_stm32::{gpio::{Flex, Level, Output, Speed}, time::Hertz};
-use embassy_time::Timer;
+use embassy_stm32::{gpio::{Flex, Level, Output, Pull, Speed}, time::Hertz};
+use embassy_time::{Delay, Timer};
 use {defmt_rtt as _, panic_probe as _};
 use embassy_stm32::Config;
 
-mod dht11;
-use dht11::Dht11;
+use dht11_dwt::dht11::Dht11;
 
 #[embassy_executor::main]
 async fn main(_spawner: Spawner) -> ! {
@@ -39,9 +38,18 @@ async fn main(_spawner: Spawner) -> ! {
 
     let mut led = Output::new(p.PB13, Level::High, Speed::VeryHigh);
 
-    // Initialize DHT11 on PA8
-    let dht_pin = Flex::new(p.PA8);
-    let mut dht11 = Dht11::new(dht_pin);
+    // Initialize DHT11 on PA8. The driver only ever calls set_high/
+    // set_low/is_high/is_low on this pin, relying on the line being open-drain
+    // with a pull-up so releasing it (set_high) lets the sensor pull it
+    // low instead of fighting a push-pull driver.
+    let mut dht_pin = Flex::new(p.PA8);
+    dht_pin.set_as_input_output(Speed::Low, Pull::Up);
+    let mut dht11 = Dht11::new(dht_pin).unwrap();
+    // `Delay` implements both the blocking `embedded-hal` `DelayNs` used
+    // for microsecond-scale bit timing and the async `embedded-hal-async`
+    // one `read_with_retries` needs for its multi-second retry spacing,
+    // so readings never busy-block the executor between attempts.
+    let mut delay = Delay;
 
     info!("DHT11 sensor initialized on PA8");
 
@@ -51,8 +59,8 @@ async fn main(_spawner: Spawner) -> ! {
     loop {
         led.toggle();
 
-        // Read DHT11 sensor
-        match dht11.read().await {
+        // Read DHT11 sensor, retrying transient failures a few times
+        match dht11.read_with_retries(&mut delay, 3).await {
             Ok(reading) => {
                 info!("Temperature: {}°C, Humidity: {}%",
                       reading.temperature, reading.humidity);
@@ -61,6 +69,9 @@ async fn main(_spawner: Spawner) -> ! {
                 info!("DHT11 read error: {:?}", e);
             }
         }
+        let stats = dht11.stats();
+        info!("stats: {} ok, {} checksum errors, {} timeouts",
+              stats.successes, stats.checksum_failures, stats.timeouts);
 
         // DHT11 needs at least 2 seconds between readings
         Timer::after_secs(2).await;